@@ -0,0 +1,63 @@
+use std::env;
+use std::net::SocketAddr;
+
+use ed25519_dalek::{PublicKey, PUBLIC_KEY_LENGTH};
+use hex::FromHex;
+
+use crate::GenericError;
+
+const DEFAULT_BIND_ADDR: &str = "127.0.0.1:3030";
+
+/// Which environment the server is running in. Only development logs the
+/// verified request body; startup checks are the same in both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Environment {
+    Development,
+    Production,
+}
+
+impl Environment {
+    pub fn is_development(self) -> bool {
+        matches!(self, Self::Development)
+    }
+}
+
+/// Application configuration loaded from the process environment (and an
+/// optional `.env` file) at startup.
+pub struct Config {
+    pub public_key: PublicKey,
+    pub bind_addr: SocketAddr,
+    pub environment: Environment,
+}
+
+impl Config {
+    pub fn from_env() -> Result<Self, GenericError> {
+        // A missing `.env` file is fine; real environment variables still work.
+        let _ = dotenvy::dotenv();
+
+        let environment = match env::var("ENVIRONMENT") {
+            Ok(value) if value.eq_ignore_ascii_case("production") => Environment::Production,
+            _ => Environment::Development,
+        };
+
+        let public_key_hex = env::var("PUBLIC_KEY")
+            .map_err(|_| "PUBLIC_KEY environment variable is required")?;
+
+        let public_key_bytes: [u8; PUBLIC_KEY_LENGTH] = FromHex::from_hex(&public_key_hex)
+            .map_err(|_| "PUBLIC_KEY must be a hex-encoded ed25519 public key")?;
+
+        let public_key = PublicKey::from_bytes(&public_key_bytes)
+            .map_err(|_| "PUBLIC_KEY is not a valid ed25519 public key")?;
+
+        let bind_addr = env::var("BIND_ADDR")
+            .unwrap_or_else(|_| DEFAULT_BIND_ADDR.to_owned())
+            .parse::<SocketAddr>()
+            .map_err(|_| "BIND_ADDR must be a valid socket address")?;
+
+        Ok(Self {
+            public_key,
+            bind_addr,
+            environment,
+        })
+    }
+}
@@ -0,0 +1,68 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use hyper::{http::StatusCode, Body, Response};
+use tower::Service;
+use twilight_model::application::{callback::InteractionResponse, interaction::Interaction};
+
+use crate::error::InteractionError;
+use crate::GenericError;
+
+/// Turns an already-authenticated [`Interaction`] into a Discord callback
+/// response, delegating `ApplicationCommand`s to a user-supplied command
+/// handler. This is the inner service wrapped by [`crate::verify::VerifyLayer`].
+#[derive(Clone)]
+pub struct Dispatcher<F> {
+    handler: F,
+}
+
+impl<F> Dispatcher<F> {
+    pub fn new(handler: F) -> Self {
+        Self { handler }
+    }
+}
+
+impl<F, Fut> Service<Interaction> for Dispatcher<F>
+where
+    F: Fn(Interaction) -> Fut + Clone + Send + 'static,
+    Fut: Future<Output = Result<InteractionResponse, GenericError>> + Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = InteractionError;
+    type Future = Pin<Box<dyn Future<Output = Result<Response<Body>, InteractionError>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, interaction: Interaction) -> Self::Future {
+        let handler = self.handler.clone();
+
+        Box::pin(async move {
+            match interaction {
+                Interaction::Ping(_) => {
+                    let json = serde_json::to_vec(&InteractionResponse::Pong)
+                        .map_err(InteractionError::SerializeResponse)?;
+
+                    Ok(Response::builder().status(StatusCode::OK).body(json.into())?)
+                }
+                Interaction::ApplicationCommand(ref cmd) => {
+                    let command_name = cmd.data.name.clone();
+                    tracing::Span::current().record("command", command_name.as_str());
+                    tracing::debug!(command = %command_name, "dispatching command");
+
+                    let response = handler(interaction).await.map_err(InteractionError::Command)?;
+                    let json =
+                        serde_json::to_vec(&response).map_err(InteractionError::SerializeResponse)?;
+
+                    Ok(Response::builder().status(StatusCode::OK).body(json.into())?)
+                }
+                _ => {
+                    tracing::warn!("rejecting request: unsupported interaction type");
+                    Err(InteractionError::UnsupportedInteraction)
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,65 @@
+use hyper::{http::StatusCode, Body, Response};
+use thiserror::Error;
+
+use crate::GenericError;
+
+/// Everything that can go wrong while authenticating and dispatching an
+/// incoming interaction, mapped to the HTTP status Discord expects back.
+#[derive(Debug, Error)]
+pub enum InteractionError {
+    #[error("method not allowed")]
+    MethodNotAllowed,
+    #[error("not found")]
+    NotFound,
+    #[error("missing or malformed x-signature-timestamp header")]
+    MissingTimestamp,
+    #[error("x-signature-timestamp header is outside the allowed freshness window")]
+    StaleTimestamp,
+    #[error("missing x-signature-ed25519 header")]
+    MissingSignature,
+    #[error("x-signature-ed25519 header is not valid hex")]
+    BadHexSignature,
+    #[error("ed25519 signature verification failed")]
+    SignatureInvalid,
+    #[error("request body is not valid JSON")]
+    MalformedBody(#[from] serde_json::Error),
+    #[error("failed to serialize interaction response")]
+    SerializeResponse(#[source] serde_json::Error),
+    #[error("unsupported interaction type")]
+    UnsupportedInteraction,
+    #[error("command handler failed: {0}")]
+    Command(#[source] GenericError),
+    #[error(transparent)]
+    Http(#[from] hyper::http::Error),
+    #[error(transparent)]
+    Body(#[from] hyper::Error),
+}
+
+impl InteractionError {
+    fn status(&self) -> StatusCode {
+        match self {
+            Self::MethodNotAllowed => StatusCode::METHOD_NOT_ALLOWED,
+            Self::NotFound => StatusCode::NOT_FOUND,
+            Self::MissingTimestamp
+            | Self::MissingSignature
+            | Self::BadHexSignature
+            | Self::MalformedBody(_)
+            | Self::UnsupportedInteraction => StatusCode::BAD_REQUEST,
+            Self::StaleTimestamp => StatusCode::UNAUTHORIZED,
+            Self::SignatureInvalid => StatusCode::FORBIDDEN,
+            Self::SerializeResponse(_)
+            | Self::Command(_)
+            | Self::Http(_)
+            | Self::Body(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl From<InteractionError> for Response<Body> {
+    fn from(error: InteractionError) -> Self {
+        Response::builder()
+            .status(error.status())
+            .body(Body::empty())
+            .unwrap_or_else(|_| Response::new(Body::empty()))
+    }
+}
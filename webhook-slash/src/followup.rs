@@ -0,0 +1,142 @@
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use hyper::client::HttpConnector;
+use hyper::header::RETRY_AFTER;
+use hyper::{Body, Client, Method, Request, StatusCode};
+use hyper_tls::HttpsConnector;
+use once_cell::sync::Lazy;
+use tracing::Instrument;
+use twilight_model::application::callback::CallbackData;
+use twilight_model::id::{ApplicationId, InteractionId};
+
+use crate::GenericError;
+
+/// Maximum number of retry attempts before giving up on a follow-up send.
+const MAX_RETRIES: u32 = 5;
+
+/// Per-attempt deadline covering both connect and response time. Neither
+/// `hyper`'s default `HttpConnector` nor `Client::request` enforce a
+/// timeout on their own, so without this a stalled connection would hang
+/// the spawned task forever instead of hitting the retry path below.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Follow-up sends that take longer than this (including retries) are logged
+/// as a warning so operators can spot slow commands.
+const SLOW_SEND_THRESHOLD: Duration = Duration::from_secs(2);
+
+type HttpsClient = Client<HttpsConnector<HttpConnector>>;
+
+/// Shared across every follow-up send so TLS connections and keep-alives to
+/// `discord.com` are reused instead of being torn down after each command.
+static HTTP_CLIENT: Lazy<HttpsClient> = Lazy::new(|| Client::builder().build(HttpsConnector::new()));
+
+/// Runs `work` in the background and delivers its result as the edited
+/// "original response" for a deferred interaction, retrying the HTTP call
+/// with exponential backoff on timeouts and connection errors, and after the
+/// `Retry-After` delay on rate limits. `interaction_id`
+/// and `command` are attached to every log event the send produces so a
+/// failure can be traced back to the command that triggered it.
+pub fn spawn_followup<Fut>(
+    application_id: ApplicationId,
+    interaction_id: InteractionId,
+    command: String,
+    token: String,
+    work: Fut,
+) where
+    Fut: Future<Output = CallbackData> + Send + 'static,
+{
+    let span = tracing::info_span!("deferred_followup", %interaction_id, %command);
+
+    tokio::spawn(
+        async move {
+            let data = work.await;
+
+            if let Err(source) = send_with_retry(application_id, &token, &data).await {
+                tracing::warn!(%source, "deferred follow-up failed after retries");
+            }
+        }
+        .instrument(span),
+    );
+}
+
+async fn send_with_retry(
+    application_id: ApplicationId,
+    token: &str,
+    data: &CallbackData,
+) -> Result<(), GenericError> {
+    let client = &*HTTP_CLIENT;
+    let uri = format!(
+        "https://discord.com/api/v10/webhooks/{}/{}/messages/@original",
+        application_id, token
+    );
+    let body = serde_json::to_vec(data)?;
+
+    let started = Instant::now();
+    let mut attempt = 0;
+
+    loop {
+        let request = Request::builder()
+            .method(Method::PATCH)
+            .uri(&uri)
+            .header("content-type", "application/json")
+            .body(Body::from(body.clone()))?;
+
+        match tokio::time::timeout(REQUEST_TIMEOUT, client.request(request)).await {
+            Ok(Ok(response)) if response.status().is_success() => break,
+            Ok(Ok(response)) if response.status() == StatusCode::TOO_MANY_REQUESTS => {
+                attempt += 1;
+                if attempt > MAX_RETRIES {
+                    return Err(format!(
+                        "follow-up rate limited after {} attempts",
+                        attempt
+                    )
+                    .into());
+                }
+
+                tokio::time::sleep(retry_after(&response)).await;
+            }
+            Ok(Ok(response)) => {
+                return Err(format!("follow-up send failed with status {}", response.status()).into());
+            }
+            Ok(Err(source)) if source.is_connect() || source.is_timeout() => {
+                attempt += 1;
+                if attempt > MAX_RETRIES {
+                    return Err(source.into());
+                }
+
+                tokio::time::sleep(backoff(attempt)).await;
+            }
+            Ok(Err(source)) => return Err(source.into()),
+            Err(_elapsed) => {
+                attempt += 1;
+                if attempt > MAX_RETRIES {
+                    return Err(format!("follow-up request timed out after {} attempts", attempt).into());
+                }
+
+                tokio::time::sleep(backoff(attempt)).await;
+            }
+        }
+    }
+
+    let elapsed = started.elapsed();
+    if elapsed > SLOW_SEND_THRESHOLD {
+        tracing::warn!(?elapsed, attempt, "deferred follow-up took longer than expected");
+    }
+
+    Ok(())
+}
+
+fn retry_after(response: &hyper::Response<Body>) -> Duration {
+    response
+        .headers()
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<f64>().ok())
+        .map(Duration::from_secs_f64)
+        .unwrap_or(Duration::from_secs(1))
+}
+
+fn backoff(attempt: u32) -> Duration {
+    Duration::from_millis(200 * 2u64.pow(attempt.min(5)))
+}
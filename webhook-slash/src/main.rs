@@ -1,105 +1,34 @@
-use ed25519_dalek::{PublicKey, Signature, Verifier, PUBLIC_KEY_LENGTH};
-use hex::FromHex;
-use once_cell::sync::Lazy;
 use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
 use twilight_model::application::{
     callback::{CallbackData, InteractionResponse},
     interaction::Interaction,
 };
 
 use hyper::{
-    http::StatusCode,
     service::{make_service_fn, service_fn},
-    Body, Method, Request, Response, Server,
+    Body, Request, Response, Server,
 };
+use tower::{Service, ServiceBuilder};
 
-type GenericError = Box<dyn std::error::Error + Send + Sync>;
-
-static PUB_KEY: Lazy<PublicKey> = Lazy::new(|| {
-    PublicKey::from_bytes(&<[u8; PUBLIC_KEY_LENGTH] as FromHex>::from_hex("PUBLIC_KEY").unwrap())
-        .unwrap()
-});
-
-async fn interaction_handler<F>(
-    req: Request<Body>,
-    f: impl Fn(Interaction) -> F,
-) -> Result<Response<Body>, GenericError>
-where
-    F: Future<Output = Result<InteractionResponse, GenericError>>,
-{
-    if req.method() != Method::POST {
-        return Ok(Response::builder()
-            .status(StatusCode::METHOD_NOT_ALLOWED)
-            .body(Body::empty())?);
-    }
-    if req.uri().path() != "/" {
-        return Ok(Response::builder()
-            .status(StatusCode::NOT_FOUND)
-            .body(Body::empty())?);
-    }
-
-    let timestamp = if let Some(ts) = req.headers().get("x-signature-timestamp") {
-        ts.to_owned()
-    } else {
-        return Ok(Response::builder()
-            .status(StatusCode::BAD_REQUEST)
-            .body(Body::empty())?);
-    };
-
-    let signature = if let Some(hex_sig) = req.headers().get("x-signature-ed25519") {
-        Signature::new(FromHex::from_hex(hex_sig)?)
-    } else {
-        return Ok(Response::builder()
-            .status(StatusCode::BAD_REQUEST)
-            .body(Body::empty())?);
-    };
-
-    let whole_body = hyper::body::to_bytes(req).await?;
-
-    if PUB_KEY
-        .verify(
-            vec![timestamp.as_bytes(), &whole_body].concat().as_ref(),
-            &signature,
-        )
-        .is_err()
-    {
-        return Ok(Response::builder()
-            .status(StatusCode::FORBIDDEN)
-            .body(Body::empty())?);
-    }
-    println!("{}", String::from_utf8(whole_body.to_vec()).unwrap());
-
-    let interaction = serde_json::from_slice::<Interaction>(&whole_body)?;
-
-    match interaction {
-        Interaction::Ping(_) => {
-            let response = InteractionResponse::Pong;
+mod config;
+mod dispatch;
+mod error;
+mod followup;
+mod verify;
 
-            let json = serde_json::to_vec(&response)?;
-
-            Ok(Response::builder()
-                .status(StatusCode::OK)
-                .body(json.into())?)
-        }
-        Interaction::ApplicationCommand(_) => {
-            let response = f(interaction).await?;
+use config::Config;
+use dispatch::Dispatcher;
+use verify::VerifyLayer;
 
-            let json = serde_json::to_vec(&response)?;
-
-            Ok(Response::builder()
-                .status(StatusCode::OK)
-                .body(json.into())?)
-        }
-        _ => Ok(Response::builder()
-            .status(StatusCode::BAD_REQUEST)
-            .body(Body::empty())?),
-    }
-}
+type GenericError = Box<dyn std::error::Error + Send + Sync>;
 
 async fn handler(i: Interaction) -> Result<InteractionResponse, GenericError> {
     match i {
         Interaction::ApplicationCommand(ref cmd) => match cmd.data.name.as_ref() {
             "vroom" => vroom(i).await,
+            "slow" => slow(i).await,
             "debug" => debug(i).await,
             _ => debug(i).await,
         },
@@ -107,6 +36,44 @@ async fn handler(i: Interaction) -> Result<InteractionResponse, GenericError> {
     }
 }
 
+/// Demonstrates the deferred response path: the command is ACKed immediately
+/// so Discord doesn't time it out, and the real content is delivered a few
+/// seconds later through the follow-up endpoint.
+async fn slow(i: Interaction) -> Result<InteractionResponse, GenericError> {
+    let cmd = match i {
+        Interaction::ApplicationCommand(cmd) => cmd,
+        _ => return Err("invalid interaction data".into()),
+    };
+
+    followup::spawn_followup(
+        cmd.application_id,
+        cmd.id,
+        cmd.data.name.clone(),
+        cmd.token.clone(),
+        async move {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+
+            CallbackData {
+                allowed_mentions: None,
+                flags: None,
+                tts: None,
+                content: Some("Done being slow!".to_owned()),
+                embeds: Default::default(),
+            }
+        },
+    );
+
+    Ok(InteractionResponse::DeferredChannelMessageWithSource(
+        CallbackData {
+            allowed_mentions: None,
+            flags: None,
+            tts: None,
+            content: None,
+            embeds: Default::default(),
+        },
+    ))
+}
+
 async fn debug(i: Interaction) -> Result<InteractionResponse, GenericError> {
     Ok(InteractionResponse::ChannelMessageWithSource(
         CallbackData {
@@ -136,10 +103,27 @@ async fn main() -> Result<(), GenericError> {
     // Initialize the tracing subscriber.
     tracing_subscriber::fmt::init();
 
-    let addr = "127.0.0.1:3030".parse().unwrap();
+    let config = Arc::new(Config::from_env()?);
+    let addr = config.bind_addr;
 
-    let interaction_service = make_service_fn(|_| async {
-        Ok::<_, GenericError>(service_fn(|req| interaction_handler(req, handler)))
+    let service = ServiceBuilder::new()
+        .layer(VerifyLayer::new(Arc::clone(&config)))
+        .service(Dispatcher::new(handler));
+
+    let interaction_service = make_service_fn(move |_| {
+        let mut service = service.clone();
+
+        async move {
+            Ok::<_, GenericError>(service_fn(move |req: Request<Body>| {
+                let mut service = service.clone();
+
+                async move {
+                    Ok::<_, GenericError>(
+                        service.call(req).await.unwrap_or_else(Response::from),
+                    )
+                }
+            }))
+        }
     });
 
     let server = Server::bind(&addr).serve(interaction_service);
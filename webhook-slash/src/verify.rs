@@ -0,0 +1,175 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ed25519_dalek::{Signature, Verifier};
+use hex::FromHex;
+use hyper::{Body, Method, Request, Response};
+use tower::{Layer, Service};
+use tracing::Instrument;
+use twilight_model::application::interaction::Interaction;
+
+use crate::config::Config;
+use crate::error::InteractionError;
+
+/// How far a request's `x-signature-timestamp` may drift from the current
+/// time, in either direction, before it's rejected as a replay.
+const TIMESTAMP_FRESHNESS_WINDOW_SECS: i64 = 300;
+
+/// A [`tower::Layer`] that authenticates incoming Discord interaction
+/// requests: it checks the request method and path, validates the
+/// `x-signature-ed25519`/`x-signature-timestamp` headers against the
+/// configured public key and freshness window, and only then forwards the
+/// parsed [`Interaction`] to the inner service. Requests that fail any of
+/// these checks never reach the inner service.
+#[derive(Clone)]
+pub struct VerifyLayer {
+    config: Arc<Config>,
+}
+
+impl VerifyLayer {
+    pub fn new(config: Arc<Config>) -> Self {
+        Self { config }
+    }
+}
+
+impl<S> Layer<S> for VerifyLayer {
+    type Service = VerifyService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        VerifyService {
+            inner,
+            config: Arc::clone(&self.config),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct VerifyService<S> {
+    inner: S,
+    config: Arc<Config>,
+}
+
+impl<S> Service<Request<Body>> for VerifyService<S>
+where
+    S: Service<Interaction, Response = Response<Body>, Error = InteractionError> + Clone + Send + 'static,
+    S::Future: Send,
+{
+    type Response = Response<Body>;
+    type Error = InteractionError;
+    type Future = Pin<Box<dyn Future<Output = Result<Response<Body>, InteractionError>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let config = Arc::clone(&self.config);
+        let mut inner = self.inner.clone();
+
+        let span = tracing::info_span!(
+            "interaction_request",
+            method = %req.method(),
+            has_signature = req.headers().contains_key("x-signature-ed25519"),
+            has_timestamp = req.headers().contains_key("x-signature-timestamp"),
+            interaction_kind = tracing::field::Empty,
+            command = tracing::field::Empty,
+        );
+
+        Box::pin(
+            async move {
+                let interaction = authenticate(req, &config).await?;
+                tracing::Span::current().record("interaction_kind", interaction_kind(&interaction));
+
+                inner.call(interaction).await
+            }
+            .instrument(span),
+        )
+    }
+}
+
+/// A short, log-friendly name for an [`Interaction`] variant.
+fn interaction_kind(interaction: &Interaction) -> &'static str {
+    match interaction {
+        Interaction::Ping(_) => "ping",
+        Interaction::ApplicationCommand(_) => "application_command",
+        Interaction::MessageComponent(_) => "message_component",
+        _ => "unsupported",
+    }
+}
+
+async fn authenticate(
+    req: Request<Body>,
+    config: &Config,
+) -> Result<Interaction, InteractionError> {
+    if req.method() != Method::POST {
+        tracing::warn!("rejecting request: method not allowed");
+        return Err(InteractionError::MethodNotAllowed);
+    }
+    if req.uri().path() != "/" {
+        tracing::warn!(path = %req.uri().path(), "rejecting request: unknown path");
+        return Err(InteractionError::NotFound);
+    }
+
+    let timestamp = req
+        .headers()
+        .get("x-signature-timestamp")
+        .ok_or_else(|| {
+            tracing::warn!("rejecting request: missing x-signature-timestamp header");
+            InteractionError::MissingTimestamp
+        })?
+        .to_owned();
+
+    let timestamp_secs = timestamp
+        .to_str()
+        .ok()
+        .and_then(|s| s.parse::<i64>().ok())
+        .ok_or_else(|| {
+            tracing::warn!("rejecting request: malformed x-signature-timestamp header");
+            InteractionError::MissingTimestamp
+        })?;
+
+    let now_secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock is before the unix epoch")
+        .as_secs() as i64;
+
+    let skew_secs = now_secs.checked_sub(timestamp_secs).map(i64::unsigned_abs);
+    if !matches!(skew_secs, Some(skew) if skew <= TIMESTAMP_FRESHNESS_WINDOW_SECS as u64) {
+        tracing::warn!(?skew_secs, "rejecting request: timestamp outside freshness window");
+        return Err(InteractionError::StaleTimestamp);
+    }
+
+    let hex_sig = req.headers().get("x-signature-ed25519").ok_or_else(|| {
+        tracing::warn!("rejecting request: missing x-signature-ed25519 header");
+        InteractionError::MissingSignature
+    })?;
+    let signature = Signature::new(FromHex::from_hex(hex_sig).map_err(|_| {
+        tracing::warn!("rejecting request: x-signature-ed25519 header is not valid hex");
+        InteractionError::BadHexSignature
+    })?);
+
+    let whole_body = hyper::body::to_bytes(req).await?;
+
+    config
+        .public_key
+        .verify(
+            vec![timestamp.as_bytes(), &whole_body].concat().as_ref(),
+            &signature,
+        )
+        .map_err(|_| {
+            tracing::warn!("rejecting request: ed25519 signature verification failed");
+            InteractionError::SignatureInvalid
+        })?;
+
+    if config.environment.is_development() {
+        tracing::debug!(body = %String::from_utf8_lossy(&whole_body), "received interaction body");
+    }
+
+    serde_json::from_slice::<Interaction>(&whole_body).map_err(|source| {
+        tracing::warn!(%source, "rejecting request: malformed interaction body");
+        InteractionError::MalformedBody(source)
+    })
+}